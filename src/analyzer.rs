@@ -0,0 +1,280 @@
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::{debug, instrument};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Node {
+    pub id: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SimilarPair {
+    pub start_id: String,
+    pub end_id: String,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeywordPair {
+    pub start_id: String,
+    pub end_id: String,
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CausalPair {
+    pub id: String,
+    pub context: String,
+    pub phrase: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HierarchicalPair {
+    pub id: String,
+    pub heading: String,
+}
+
+/// The combined result of analyzing a set of nodes, regardless of which `GraphAnalyzer`
+/// backend produced it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnalysisOutput {
+    pub similar_pairs: Vec<SimilarPair>,
+    pub keyword_pairs: Vec<KeywordPair>,
+    pub causal_pairs: Vec<CausalPair>,
+    pub hierarchical_pairs: Vec<HierarchicalPair>,
+}
+
+#[derive(Error, Debug)]
+pub enum AnalyzerError {
+    #[error("IO error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] reqwest::Error),
+    #[error("Python script error: {0}")]
+    PythonScriptError(String),
+}
+
+/// A pluggable backend for turning a batch of nodes into the pair sets the enrichment
+/// pipeline uses to create relationships.
+#[async_trait]
+pub trait GraphAnalyzer {
+    async fn analyze(&self, nodes: &[Node]) -> Result<AnalysisOutput, AnalyzerError>;
+}
+
+/// Shells out to the original `vectorize_and_analyze.py` script, handing nodes over and
+/// reading results back through a pair of temp files.
+pub struct PythonSubprocessAnalyzer {
+    script_path: String,
+    input_file_path: String,
+    output_file_path: String,
+}
+
+impl PythonSubprocessAnalyzer {
+    pub fn new(script_path: impl Into<String>, input_file_path: impl Into<String>, output_file_path: impl Into<String>) -> Self {
+        Self {
+            script_path: script_path.into(),
+            input_file_path: input_file_path.into(),
+            output_file_path: output_file_path.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl GraphAnalyzer for PythonSubprocessAnalyzer {
+    #[instrument(skip(self, nodes), fields(node_count = nodes.len()))]
+    async fn analyze(&self, nodes: &[Node]) -> Result<AnalysisOutput, AnalyzerError> {
+        if !std::path::Path::new(&self.script_path).exists() {
+            return Err(AnalyzerError::IoError(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Python script not found",
+            )));
+        }
+
+        let node_data_json = serde_json::to_string(nodes)?;
+        debug!("Node data JSON: {}", node_data_json);
+        std::fs::write(&self.input_file_path, node_data_json)?;
+
+        let script_path = self.script_path.clone();
+        let input_file_path = self.input_file_path.clone();
+        let output_file_path = self.output_file_path.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<(), AnalyzerError> {
+            let mut child = Command::new("python3")
+                .arg(&script_path)
+                .arg(&input_file_path)
+                .arg(&output_file_path)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to run vectorize_and_analyze.py");
+
+            let stdout = BufReader::new(child.stdout.take().expect("Failed to capture stdout"));
+            let stderr = BufReader::new(child.stderr.take().expect("Failed to capture stderr"));
+
+            let stdout_thread = std::thread::spawn(move || {
+                for line in stdout.lines().map_while(Result::ok) {
+                    debug!("STDOUT: {}", line);
+                }
+            });
+            let stderr_thread = std::thread::spawn(move || {
+                for line in stderr.lines().map_while(Result::ok) {
+                    debug!("STDERR: {}", line);
+                }
+            });
+
+            stdout_thread.join().expect("Failed to join stdout thread");
+            stderr_thread.join().expect("Failed to join stderr thread");
+
+            if !child.wait()?.success() {
+                return Err(AnalyzerError::PythonScriptError("Python script failed".into()));
+            }
+            Ok(())
+        })
+        .await
+        .expect("analyzer subprocess task panicked")?;
+
+        let output_data = std::fs::read_to_string(&self.output_file_path)?;
+        Ok(serde_json::from_str(&output_data)?)
+    }
+}
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    nodes: &'a [Node],
+}
+
+#[derive(Deserialize)]
+struct EmbedResponse {
+    embeddings: Vec<NodeEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct NodeEmbedding {
+    id: String,
+    vector: Vec<f64>,
+}
+
+/// Default cosine-similarity threshold above which a node pair is emitted as a `SimilarPair`.
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Posts node contents to a configurable embedding endpoint and computes similarity pairs
+/// in Rust, eliminating the Python subprocess and its temp-file handoff. Only emits
+/// `similar_pairs`; keyword/causal/hierarchical extraction is not performed by this backend.
+pub struct HttpEmbeddingAnalyzer {
+    endpoint: String,
+    threshold: f64,
+    client: reqwest::Client,
+}
+
+impl HttpEmbeddingAnalyzer {
+    pub fn new(endpoint: impl Into<String>, threshold: f64) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            threshold,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+fn l2_normalize(vector: &[f64]) -> Vec<f64> {
+    let norm = vector.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|v| v / norm).collect()
+}
+
+fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+#[async_trait]
+impl GraphAnalyzer for HttpEmbeddingAnalyzer {
+    #[instrument(skip(self, nodes), fields(node_count = nodes.len()))]
+    async fn analyze(&self, nodes: &[Node]) -> Result<AnalysisOutput, AnalyzerError> {
+        debug!("Posting {} nodes to embedding endpoint {}", nodes.len(), self.endpoint);
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(&EmbedRequest { nodes })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<EmbedResponse>()
+            .await?;
+
+        let normalized: Vec<(String, Vec<f64>)> = response
+            .embeddings
+            .into_iter()
+            .map(|e| (e.id, l2_normalize(&e.vector)))
+            .collect();
+
+        let mut similar_pairs = Vec::new();
+        for i in 0..normalized.len() {
+            for j in (i + 1)..normalized.len() {
+                let (start_id, start_vec) = &normalized[i];
+                let (end_id, end_vec) = &normalized[j];
+                let similarity = cosine_similarity(start_vec, end_vec);
+                if similarity > self.threshold {
+                    similar_pairs.push(SimilarPair {
+                        start_id: start_id.clone(),
+                        end_id: end_id.clone(),
+                        similarity,
+                    });
+                }
+            }
+        }
+
+        Ok(AnalysisOutput {
+            similar_pairs,
+            keyword_pairs: Vec::new(),
+            causal_pairs: Vec::new(),
+            hierarchical_pairs: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn l2_normalize_scales_to_unit_length() {
+        let normalized = l2_normalize(&[3.0, 4.0]);
+        assert!((normalized[0] - 0.6).abs() < 1e-9);
+        assert!((normalized[1] - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn l2_normalize_leaves_zero_vector_untouched() {
+        assert_eq!(l2_normalize(&[0.0, 0.0]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn cosine_similarity_of_identical_unit_vectors_is_one() {
+        let v = l2_normalize(&[1.0, 2.0, 3.0]);
+        assert!((cosine_similarity(&v, &v) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cosine_similarity_of_orthogonal_vectors_is_zero() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[0.0, 1.0])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn similar_vectors_exceed_the_default_threshold() {
+        // `HttpEmbeddingAnalyzer` only keeps pairs with similarity strictly greater than the
+        // threshold, so identical (and thus maximally similar) vectors must clear it.
+        let v = l2_normalize(&[1.0, 1.0]);
+        let similarity = cosine_similarity(&v, &v);
+        assert!(similarity > DEFAULT_SIMILARITY_THRESHOLD);
+    }
+}
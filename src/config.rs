@@ -0,0 +1,227 @@
+use std::env;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::analyzer::DEFAULT_SIMILARITY_THRESHOLD;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("missing required environment variable: {0}")]
+    MissingVar(String),
+    #[error("invalid value for {var}: {value}")]
+    InvalidValue { var: String, value: String },
+    #[error("{0}")]
+    Validation(String),
+}
+
+/// Which `GraphAnalyzer` backend to use, selected via `RFNU_ANALYZER`. `Http` carries its
+/// endpoint so that a constructed `Config` can never represent an `Http` backend without one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnalyzerBackend {
+    Python,
+    Http(String),
+}
+
+/// All connection, credential, path, and threshold settings the pipeline needs, loaded from
+/// the environment (and a `.env` file, if present) instead of being hardcoded.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub neo4j_uri: String,
+    pub neo4j_user: String,
+    pub neo4j_password: String,
+    pub neo4j_database: String,
+    pub analyzer_backend: AnalyzerBackend,
+    pub similarity_threshold: f64,
+    pub script_path: String,
+    pub input_file_path: String,
+    pub output_file_path: String,
+    pub rdf_output_path: String,
+    pub batch_size: usize,
+    pub metrics_addr: SocketAddr,
+}
+
+fn var_or(key: &str, default: &str) -> String {
+    env::var(key).unwrap_or_else(|_| default.to_string())
+}
+
+/// Parses `key` with `T::from_str` if set, falling back to `default` if unset, and reporting an
+/// `InvalidValue` error if the value is set but doesn't parse.
+fn parse_env<T: FromStr>(key: &str, default: T) -> Result<T, ConfigError> {
+    match env::var(key) {
+        Ok(raw) => raw.parse::<T>().map_err(|_| ConfigError::InvalidValue {
+            var: key.to_string(),
+            value: raw,
+        }),
+        Err(_) => Ok(default),
+    }
+}
+
+impl Config {
+    /// Loads configuration from a `.env` file (if present) and the process environment,
+    /// failing fast with a `ConfigError` rather than panicking on a missing file or silently
+    /// proceeding with an unusable setting.
+    pub fn from_env() -> Result<Self, ConfigError> {
+        // Skipped under test: it reads the filesystem `.env`, not just the process environment,
+        // so `cargo test` would see a dev's local `.env` and make validation tests non-deterministic.
+        #[cfg(not(test))]
+        dotenvy::dotenv().ok();
+
+        let neo4j_uri = var_or("RFNU_NEO4J_URI", "bolt://localhost:7687");
+        let neo4j_user = var_or("RFNU_NEO4J_USER", "neo4j");
+        let neo4j_password = env::var("RFNU_NEO4J_PASSWORD")
+            .map_err(|_| ConfigError::MissingVar("RFNU_NEO4J_PASSWORD".to_string()))?;
+        let neo4j_database = var_or("RFNU_NEO4J_DATABASE", "neo4j");
+
+        let analyzer_backend = match var_or("RFNU_ANALYZER", "python").as_str() {
+            "http" => {
+                let endpoint = env::var("RFNU_EMBEDDING_ENDPOINT").map_err(|_| {
+                    ConfigError::Validation(
+                        "RFNU_EMBEDDING_ENDPOINT is required when RFNU_ANALYZER=http".to_string(),
+                    )
+                })?;
+                AnalyzerBackend::Http(endpoint)
+            }
+            "python" => AnalyzerBackend::Python,
+            other => {
+                return Err(ConfigError::InvalidValue {
+                    var: "RFNU_ANALYZER".to_string(),
+                    value: other.to_string(),
+                })
+            }
+        };
+
+        let similarity_threshold = parse_env("RFNU_SIMILARITY_THRESHOLD", DEFAULT_SIMILARITY_THRESHOLD)?;
+
+        let script_path = var_or("RFNU_SCRIPT_PATH", "vectorize_and_analyze.py");
+        let input_file_path = var_or("RFNU_INPUT_FILE", "temp_input.json");
+        let output_file_path = var_or("RFNU_OUTPUT_FILE", "temp_output.json");
+        let rdf_output_path = var_or("RFNU_RDF_OUTPUT_FILE", "graph.nt");
+
+        let batch_size = parse_env("RFNU_BATCH_SIZE", 1000usize)?;
+
+        let default_metrics_addr: SocketAddr = "127.0.0.1:9898".parse().unwrap();
+        let metrics_addr = parse_env("RFNU_METRICS_ADDR", default_metrics_addr)?;
+
+        if neo4j_password.is_empty() {
+            return Err(ConfigError::Validation("RFNU_NEO4J_PASSWORD must not be empty".to_string()));
+        }
+        if batch_size == 0 {
+            return Err(ConfigError::Validation("RFNU_BATCH_SIZE must be greater than zero".to_string()));
+        }
+
+        Ok(Self {
+            neo4j_uri,
+            neo4j_user,
+            neo4j_password,
+            neo4j_database,
+            analyzer_backend,
+            similarity_threshold,
+            script_path,
+            input_file_path,
+            output_file_path,
+            rdf_output_path,
+            batch_size,
+            metrics_addr,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // `Config::from_env` reads process-global environment variables, so tests that set them
+    // must not run concurrently with each other (cargo test runs tests in parallel by default).
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    const RFNU_VARS: &[&str] = &[
+        "RFNU_NEO4J_URI",
+        "RFNU_NEO4J_USER",
+        "RFNU_NEO4J_PASSWORD",
+        "RFNU_NEO4J_DATABASE",
+        "RFNU_ANALYZER",
+        "RFNU_EMBEDDING_ENDPOINT",
+        "RFNU_SIMILARITY_THRESHOLD",
+        "RFNU_SCRIPT_PATH",
+        "RFNU_INPUT_FILE",
+        "RFNU_OUTPUT_FILE",
+        "RFNU_RDF_OUTPUT_FILE",
+        "RFNU_BATCH_SIZE",
+        "RFNU_METRICS_ADDR",
+    ];
+
+    /// Clears every `RFNU_*` var so each test starts from a blank slate regardless of what the
+    /// previous test (or the host environment) left behind.
+    fn clear_env() {
+        for var in RFNU_VARS {
+            env::remove_var(var);
+        }
+    }
+
+    #[test]
+    fn empty_password_is_rejected() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("RFNU_NEO4J_PASSWORD", "");
+
+        let err = Config::from_env().unwrap_err();
+        assert!(matches!(err, ConfigError::Validation(_)));
+    }
+
+    #[test]
+    fn missing_password_is_rejected() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+
+        let err = Config::from_env().unwrap_err();
+        assert!(matches!(err, ConfigError::MissingVar(var) if var == "RFNU_NEO4J_PASSWORD"));
+    }
+
+    #[test]
+    fn http_analyzer_without_endpoint_is_rejected() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("RFNU_NEO4J_PASSWORD", "hunter2");
+        env::set_var("RFNU_ANALYZER", "http");
+
+        let err = Config::from_env().unwrap_err();
+        assert!(matches!(err, ConfigError::Validation(_)));
+    }
+
+    #[test]
+    fn http_analyzer_with_endpoint_is_accepted() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("RFNU_NEO4J_PASSWORD", "hunter2");
+        env::set_var("RFNU_ANALYZER", "http");
+        env::set_var("RFNU_EMBEDDING_ENDPOINT", "http://localhost:8000/embed");
+
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.analyzer_backend, AnalyzerBackend::Http("http://localhost:8000/embed".to_string()));
+    }
+
+    #[test]
+    fn zero_batch_size_is_rejected() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("RFNU_NEO4J_PASSWORD", "hunter2");
+        env::set_var("RFNU_BATCH_SIZE", "0");
+
+        let err = Config::from_env().unwrap_err();
+        assert!(matches!(err, ConfigError::Validation(_)));
+    }
+
+    #[test]
+    fn invalid_metrics_addr_is_rejected() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        env::set_var("RFNU_NEO4J_PASSWORD", "hunter2");
+        env::set_var("RFNU_METRICS_ADDR", "not-a-socket-addr");
+
+        let err = Config::from_env().unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { var, .. } if var == "RFNU_METRICS_ADDR"));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod analyzer;
+pub mod config;
+pub mod metrics;
+pub mod neo4j_client;
+pub mod rdf;
@@ -1,200 +1,178 @@
-mod neo4j_client;
-
-use log::{debug, info, error};
-use env_logger::Env;
-use std::process::{Command, Stdio};
-use std::io::{Write, BufRead, BufReader};
-use std::env;
-use serde_json::json;
+use std::collections::HashMap;
+use std::time::Instant;
 use thiserror::Error;
-use tokio::process::Command as TokioCommand;
-use neo4j_client::{Neo4jClient, Neo4jClientError};
-
-#[derive(Debug, serde::Deserialize, serde::Serialize)]
-struct Node {
-    id: String,
-    content: String,
-}
-
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct SimilarPair {
-    start_id: String,
-    end_id: String,
-    similarity: f64,
-}
-
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct KeywordPair {
-    start_id: String,
-    end_id: String,
-    keywords: Vec<String>,
-}
-
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct CausalPair {
-    id: String,
-    context: String,
-    phrase: String,
-}
-
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-struct HierarchicalPair {
-    id: String,
-    heading: String,
-}
+use tracing::{debug, error, info};
+use rfnu::analyzer::{AnalyzerError, GraphAnalyzer, HttpEmbeddingAnalyzer, Node, PythonSubprocessAnalyzer};
+use rfnu::config::{AnalyzerBackend, Config, ConfigError};
+use rfnu::neo4j_client::{Neo4jClient, Neo4jClientError};
+use rfnu::rdf::{self, RdfError};
+use rfnu::metrics;
 
 #[derive(Error, Debug)]
 pub enum MainError {
+    #[error("configuration error: {0}")]
+    ConfigError(#[from] ConfigError),
     #[error("Neo4j client error: {0}")]
     Neo4jClientError(#[from] Neo4jClientError),
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
-    #[error("Python script error: {0}")]
-    PythonScriptError(String),
+    #[error("Analyzer error: {0}")]
+    AnalyzerError(#[from] AnalyzerError),
     #[error("JSON error: {0}")]
     JsonError(#[from] serde_json::Error),
+    #[error("RDF export error: {0}")]
+    RdfError(#[from] RdfError),
 }
 
-#[tokio::main]
-async fn main() -> Result<(), MainError> {
-    env_logger::Builder::from_env(Env::default().default_filter_or("debug")).init();
-
-    let client = Neo4jClient::new("bolt://localhost:7687", "neo4j", "system2024!", "neo4j").await?;
-
-    info!("Querying nodes...");
-    let nodes = client.query_nodes().await?;
-    let node_data: Vec<_> = nodes.iter().map(|node| {
-        json!({
-            "id": node.properties.iter().find(|prop| prop.name == "id").map(|prop| &prop.value).unwrap_or(&String::new()),
-            "content": node.properties.iter().find(|prop| prop.name == "content").map(|prop| &prop.value).unwrap_or(&String::new())
-        })
-    }).collect();
-
-    let node_data_json = serde_json::to_string(&node_data)?;
-    debug!("Node data JSON: {}", node_data_json);
-
-    info!("Running vector analysis...");
+/// Builds the `GraphAnalyzer` backend selected by `config.analyzer_backend`.
+fn select_analyzer(config: &Config) -> Box<dyn GraphAnalyzer> {
+    match &config.analyzer_backend {
+        AnalyzerBackend::Http(endpoint) => {
+            Box::new(HttpEmbeddingAnalyzer::new(endpoint.clone(), config.similarity_threshold))
+        }
+        AnalyzerBackend::Python => Box::new(PythonSubprocessAnalyzer::new(
+            config.script_path.clone(),
+            config.input_file_path.clone(),
+            config.output_file_path.clone(),
+        )),
+    }
+}
 
-    let script_path = "/Users/n/RustroverProjects/rfnu/src/vectorize_and_analyze.py";
-    debug!("Script path: {:?}", script_path);
+/// Records per-`rel_type` created/failed counts for one batch, using each pair's own
+/// `rel_type` as the metric label rather than the outer batch category. This matters for the
+/// causal batch: its pairs don't share a single relationship type (each is derived from
+/// `pair.phrase`), so lumping them all under one label would hide a failing causal sub-type
+/// next to healthy ones.
+fn record_batch_metrics(batch: &[(String, String, String)], skipped: &[(String, String, String)]) {
+    let mut skipped_by_type: HashMap<&str, u64> = HashMap::new();
+    for (_, _, rel_type) in skipped {
+        *skipped_by_type.entry(rel_type.as_str()).or_insert(0) += 1;
+    }
 
-    if !std::path::Path::new(script_path).exists() {
-        return Err(MainError::IoError(std::io::Error::new(std::io::ErrorKind::NotFound, "Python script not found")));
+    let mut total_by_type: HashMap<&str, u64> = HashMap::new();
+    for (_, _, rel_type) in batch {
+        *total_by_type.entry(rel_type.as_str()).or_insert(0) += 1;
     }
 
-    let input_file_path = "/Users/n/RustroverProjects/rfnu/temp_input.json";
-    let output_file_path = "/Users/n/RustroverProjects/rfnu/temp_output.json";
-
-    std::fs::write(input_file_path, node_data_json)?;
-
-    let mut child = Command::new("python3")
-        .arg(script_path)
-        .arg(input_file_path)
-        .arg(output_file_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .expect("Failed to run vectorize_and_analyze.py");
-
-    let stdout = BufReader::new(child.stdout.take().expect("Failed to capture stdout"));
-    let stderr = BufReader::new(child.stderr.take().expect("Failed to capture stderr"));
-
-    let stdout_thread = std::thread::spawn(move || {
-        let mut output = String::new();
-        for line in stdout.lines() {
-            if let Ok(line) = line {
-                println!("STDOUT: {}", line);
-                output.push_str(&line);
-                output.push('\n');
-            }
+    for (rel_type, total) in total_by_type {
+        let failed = skipped_by_type.get(rel_type).copied().unwrap_or(0);
+        let created = total - failed;
+        if created > 0 {
+            metrics::RELATIONSHIPS_TOTAL.with_label_values(&[rel_type, "created"]).inc_by(created);
         }
-        output
-    });
+        if failed > 0 {
+            metrics::RELATIONSHIPS_TOTAL.with_label_values(&[rel_type, "failed"]).inc_by(failed);
+        }
+    }
+}
 
-    let stderr_thread = std::thread::spawn(move || {
-        for line in stderr.lines() {
-            if let Ok(line) = line {
-                eprintln!("STDERR: {}", line);
+/// Creates `batch` in chunks of `config.batch_size` so a single run can't hand Neo4j an
+/// unbounded `UNWIND` payload.
+async fn create_relationships(
+    client: &Neo4jClient,
+    label: &str,
+    batch: &[(String, String, String)],
+    config: &Config,
+) -> Result<(), Neo4jClientError> {
+    for chunk in batch.chunks(config.batch_size) {
+        info!("Creating {} {} relationships in one batch", chunk.len(), label);
+        match client.create_relationships_batch(chunk).await {
+            Ok(skipped) => {
+                if !skipped.is_empty() {
+                    error!("Skipped {} {} relationship(s) due to invalid type or missing node(s)", skipped.len(), label);
+                }
+                record_batch_metrics(chunk, &skipped);
+            }
+            Err(e) => {
+                error!("Failed to create {} relationship batch: {:?}", label, e);
+                record_batch_metrics(chunk, chunk);
             }
         }
-    });
+    }
+    Ok(())
+}
 
-    stdout_thread.join().expect("Failed to join stdout thread");
-    stderr_thread.join().expect("Failed to join stderr thread");
+#[tokio::main]
+async fn main() -> Result<(), MainError> {
+    tracing_subscriber::fmt::init();
 
-    debug!("Finished running vector analysis");
+    let config = Config::from_env()?;
 
-    if !child.wait()?.success() {
-        return Err(MainError::PythonScriptError("Python script failed".into()));
-    }
+    metrics::spawn_metrics_server(config.metrics_addr);
+    info!("Serving Prometheus metrics on {}", config.metrics_addr);
+
+    let client = Neo4jClient::new(&config.neo4j_uri, &config.neo4j_user, &config.neo4j_password, &config.neo4j_database).await?;
+
+    info!("Querying nodes...");
+    let query_results = client.query_nodes().await?;
+    let nodes: Vec<Node> = query_results.iter().map(|node| Node {
+        id: node.properties.iter().find(|prop| prop.name == "id").map(|prop| prop.value.clone()).unwrap_or_default(),
+        content: node.properties.iter().find(|prop| prop.name == "content").map(|prop| prop.value.clone()).unwrap_or_default(),
+    }).collect();
+    debug!("Node data: {:?}", nodes);
+    metrics::NODES_PROCESSED.set(nodes.len() as i64);
 
-    let output_data = std::fs::read_to_string(output_file_path)?;
-    let result: serde_json::Value = serde_json::from_str(&output_data)?;
+    info!("Running analysis...");
+    let analyzer = select_analyzer(&config);
+    let analysis_started = Instant::now();
+    let analysis = analyzer.analyze(&nodes).await?;
+    metrics::ANALYSIS_DURATION_SECONDS.observe(analysis_started.elapsed().as_secs_f64());
 
-    let similar_pairs: Vec<SimilarPair> = serde_json::from_value(result["similar_pairs"].clone())?;
-    let keyword_pairs: Vec<KeywordPair> = serde_json::from_value(result["keyword_pairs"].clone())?;
-    let causal_pairs: Vec<CausalPair> = serde_json::from_value(result["causal_pairs"].clone())?;
-    let hierarchical_pairs: Vec<HierarchicalPair> = serde_json::from_value(result["hierarchical_pairs"].clone())?;
+    let rfnu::analyzer::AnalysisOutput { similar_pairs, keyword_pairs, causal_pairs, hierarchical_pairs } = analysis;
 
     info!("Creating new relationships...");
-    for pair in similar_pairs {
-        if let Some(start_node_id) = client.get_internal_node_id(&pair.start_id).await? {
-            if let Some(end_node_id) = client.get_internal_node_id(&pair.end_id).await? {
-                debug!("Creating SIMILAR_TO relationship between {} and {}", start_node_id, end_node_id);
-                if let Err(e) = client.create_relationship(start_node_id, end_node_id, "SIMILAR_TO").await {
-                    error!("Failed to create relationship between {} and {}: {:?}", pair.start_id, pair.end_id, e);
-                }
-            }
-        }
-    }
 
-    for pair in keyword_pairs {
-        if let Some(start_node_id) = client.get_internal_node_id(&pair.start_id).await? {
-            if let Some(end_node_id) = client.get_internal_node_id(&pair.end_id).await? {
-                debug!("Creating KEYWORD_OVERLAP relationship between {} and {}", start_node_id, end_node_id);
-                if let Err(e) = client.create_relationship(start_node_id, end_node_id, "KEYWORD_OVERLAP").await {
-                    error!("Failed to create relationship between {} and {}: {:?}", pair.start_id, pair.end_id, e);
-                }
-            }
-        }
-    }
+    // `create_relationships_batch` diffs the nodes the `CREATE` actually matched against the
+    // pairs requested, so it already reports missing-node pairs; there is no need to spend a
+    // network round trip per pair re-confirming a node exists before handing it to the batch.
+    let similar_to_pairs: Vec<_> = similar_pairs
+        .into_iter()
+        .map(|pair| (pair.start_id, pair.end_id, "SIMILAR_TO".to_string()))
+        .collect();
+
+    let keyword_overlap_pairs: Vec<_> = keyword_pairs
+        .into_iter()
+        .map(|pair| (pair.start_id, pair.end_id, "KEYWORD_OVERLAP".to_string()))
+        .collect();
 
+    let mut causal_rel_pairs = Vec::new();
     for pair in causal_pairs {
         debug!("Processing causal pair: {:?}", pair);
         if let Some(start_node_id) = client.get_internal_node_id_by_content(&pair.context).await? {
-            debug!("Start node ID for causal pair: {}", start_node_id);
-            if let Some(end_node_id) = client.get_internal_node_id(&pair.id).await? {
-                debug!("End node ID for causal pair: {}", end_node_id);
-                let rel_type = pair.phrase.replace(' ', "_").to_uppercase(); // Convert phrase to suitable relationship type
-                debug!("Creating {} relationship between {} and {}", rel_type, start_node_id, end_node_id);
-                if let Err(e) = client.create_relationship(start_node_id, end_node_id, &rel_type).await {
-                    error!("Failed to create causal relationship for node {}: {:?}", pair.id, e);
-                }
-            }
+            let rel_type = pair.phrase.replace(' ', "_").to_uppercase(); // Convert phrase to suitable relationship type
+            causal_rel_pairs.push((start_node_id, pair.id, rel_type));
         }
     }
 
+    let mut part_of_pairs = Vec::new();
     for pair in hierarchical_pairs {
         debug!("Processing hierarchical pair: {:?}", pair);
         if let Some(start_node_id) = client.get_internal_node_id_by_content(&pair.heading).await? {
-            debug!("Start node ID for hierarchical pair: {}", start_node_id);
-            if let Some(end_node_id) = client.get_internal_node_id(&pair.id).await? {
-                debug!("End node ID for hierarchical pair: {}", end_node_id);
-                debug!("Creating PART_OF relationship between {} and {}", start_node_id, end_node_id);
-                if let Err(e) = client.create_relationship(start_node_id, end_node_id, "PART_OF").await {
-                    error!("Failed to create hierarchical relationship for node {}: {:?}", pair.id, e);
-                }
-            }
+            part_of_pairs.push((start_node_id, pair.id, "PART_OF".to_string()));
         }
     }
 
-    info!("Done");
-
+    create_relationships(&client, "SIMILAR_TO", &similar_to_pairs, &config).await?;
+    create_relationships(&client, "KEYWORD_OVERLAP", &keyword_overlap_pairs, &config).await?;
+    create_relationships(&client, "causal", &causal_rel_pairs, &config).await?;
+    create_relationships(&client, "PART_OF", &part_of_pairs, &config).await?;
+
+    info!("Exporting graph to RDF...");
+    let rdf_relationships = client.query_relationships_with_endpoints().await?;
+    match rdf::RdfStore::from_graph(&query_results, &rdf_relationships) {
+        Ok(store) => {
+            std::fs::write(&config.rdf_output_path, store.to_ntriples()?)?;
+            info!("Wrote {} nodes and {} relationships to {}", query_results.len(), rdf_relationships.len(), config.rdf_output_path);
+
+            match store.run_sparql("SELECT (COUNT(*) AS ?triples) WHERE { ?s ?p ?o }") {
+                Ok(result) => info!("RDF store triple count: {}", result.trim()),
+                Err(e) => error!("Failed to run SPARQL triple count query: {:?}", e),
+            }
+        }
+        Err(e) => error!("Failed to build RDF store: {:?}", e),
+    }
 
     info!("Done");
 
     Ok(())
 }
-
-
-
@@ -0,0 +1,56 @@
+use std::net::SocketAddr;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use once_cell::sync::Lazy;
+use prometheus::{Encoder, Histogram, HistogramOpts, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Relationships created or failed, labeled by relationship type and outcome (`created`/`failed`).
+pub static RELATIONSHIPS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = IntCounterVec::new(
+        Opts::new("rfnu_relationships_total", "Relationships created or failed, by type and outcome"),
+        &["rel_type", "outcome"],
+    )
+    .unwrap();
+    REGISTRY.register(Box::new(counter.clone())).unwrap();
+    counter
+});
+
+/// Time spent inside the `GraphAnalyzer` backend per run.
+pub static ANALYSIS_DURATION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    let histogram = Histogram::with_opts(HistogramOpts::new(
+        "rfnu_analysis_duration_seconds",
+        "Time spent in the analyzer backend",
+    ))
+    .unwrap();
+    REGISTRY.register(Box::new(histogram.clone())).unwrap();
+    histogram
+});
+
+/// Number of nodes queried in the most recent run.
+pub static NODES_PROCESSED: Lazy<IntGauge> = Lazy::new(|| {
+    let gauge = IntGauge::new("rfnu_nodes_processed", "Nodes queried in the most recent run").unwrap();
+    REGISTRY.register(Box::new(gauge.clone())).unwrap();
+    gauge
+});
+
+async fn serve_metrics(_req: Request<Body>) -> Result<Response<Body>, hyper::Error> {
+    let encoder = TextEncoder::new();
+    let metric_families = REGISTRY.gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).unwrap();
+    Ok(Response::new(Body::from(buffer)))
+}
+
+/// Spawns a small `/metrics` endpoint for operators to scrape with Prometheus.
+pub fn spawn_metrics_server(addr: SocketAddr) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let make_svc = make_service_fn(|_conn| async { Ok::<_, hyper::Error>(service_fn(serve_metrics)) });
+        let server = Server::bind(&addr).serve(make_svc);
+        if let Err(e) = server.await {
+            tracing::error!("metrics server error: {}", e);
+        }
+    })
+}
@@ -1,7 +1,22 @@
-use neo4rs::{Graph, query, ConfigBuilder, Node, Relation, Query};
+use neo4rs::{BoltList, BoltMap, BoltString, BoltType, Graph, query, ConfigBuilder, Node, Relation, Query};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use log::{debug, error};
+use tracing::{debug, error, instrument};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Relationship types cannot be bound as Cypher parameters, so any type that
+/// is interpolated into a query string must match this allowlist first.
+static REL_TYPE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Z_][A-Z0-9_]*$").unwrap());
+
+fn validate_rel_type(rel_type: &str) -> Result<(), Neo4jClientError> {
+    if REL_TYPE_RE.is_match(rel_type) {
+        Ok(())
+    } else {
+        Err(Neo4jClientError::InvalidRelType(rel_type.to_string()))
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct QueryResult {
@@ -15,12 +30,23 @@ pub struct Property {
     pub value: String,
 }
 
+/// A relationship together with the `id` property of the nodes it connects.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelationshipRecord {
+    pub start_id: String,
+    pub end_id: String,
+    pub rel_type: String,
+    pub properties: Vec<Property>,
+}
+
 #[derive(Error, Debug)]
 pub enum Neo4jClientError {
     #[error("Neo4j error: {0}")]
     Neo4jError(#[from] neo4rs::Error),
     #[error("Other error: {0}")]
     OtherError(String),
+    #[error("Invalid relationship type: {0}")]
+    InvalidRelType(String),
 }
 
 pub struct Neo4jClient {
@@ -40,6 +66,7 @@ impl Neo4jClient {
         Ok(Neo4jClient { graph })
     }
 
+    #[instrument(skip(self))]
     pub async fn query_nodes(&self) -> Result<Vec<QueryResult>, Neo4jClientError> {
         let query_str = "MATCH (n) RETURN n";
         let mut result = self.graph.execute(query(query_str)).await?;
@@ -64,6 +91,7 @@ impl Neo4jClient {
                 properties: props,
             });
         }
+        debug!(node_count = query_results.len(), "queried nodes");
         Ok(query_results)
     }
 
@@ -94,16 +122,64 @@ impl Neo4jClient {
         Ok(query_results)
     }
 
+    /// Like `query_relationships`, but also resolves each endpoint's `id` property so callers
+    /// (e.g. the `rdf` export module) can build subject/object references without a second
+    /// round trip through the internal Neo4j node id.
+    pub async fn query_relationships_with_endpoints(&self) -> Result<Vec<RelationshipRecord>, Neo4jClientError> {
+        let query_str = "MATCH (a)-[r]->(b) RETURN a.id AS start_id, b.id AS end_id, type(r) AS rel_type, r AS r";
+        let mut result = self.graph.execute(query(query_str)).await?;
+        let mut records = Vec::new();
+
+        while let Ok(Some(row)) = result.next().await {
+            let start_id: String = row.get("start_id").unwrap_or_default();
+            let end_id: String = row.get("end_id").unwrap_or_default();
+            let rel_type: String = row.get("rel_type").unwrap_or_default();
+            let relationship: Relation = row.get("r").unwrap();
+
+            let mut props = Vec::new();
+            for key in relationship.keys() {
+                match relationship.get::<String>(key) {
+                    Ok(value) => {
+                        props.push(Property {
+                            name: key.to_string(),
+                            value,
+                        });
+                    }
+                    Err(_) => continue,
+                }
+            }
+
+            records.push(RelationshipRecord {
+                start_id,
+                end_id,
+                rel_type,
+                properties: props,
+            });
+        }
+        Ok(records)
+    }
+
     pub async fn check_node_exists(&self, node_id: &str) -> Result<bool, Neo4jClientError> {
-        let query_str = format!("MATCH (n {{id: '{}'}}) RETURN n", node_id);
-        let q = query(&query_str);
-        debug!("Check node existence Query_string: {}", query_str);
+        let q = query("MATCH (n {id: $id}) RETURN n").param("id", node_id);
+        debug!("Check node existence for id: {}", node_id);
 
         let mut result = self.graph.execute(q).await?;
         Ok(result.next().await?.is_some())
     }
 
+    /// Resolves a node's `id` property, confirming it exists before callers use it to create a
+    /// relationship. Returns `None` if no node with that id is in the graph.
+    pub async fn get_internal_node_id(&self, id: &str) -> Result<Option<String>, Neo4jClientError> {
+        if self.check_node_exists(id).await? {
+            Ok(Some(id.to_string()))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub async fn create_relationship(&self, start_id: &str, end_id: &str, rel_type: &str) -> Result<(), Neo4jClientError> {
+        validate_rel_type(rel_type)?;
+
         // Check if both nodes exist
         if !self.check_node_exists(start_id).await? {
             error!("Node with id {} does not exist", start_id);
@@ -115,12 +191,15 @@ impl Neo4jClient {
             return Err(Neo4jClientError::OtherError(format!("Node with id {} does not exist", end_id)));
         }
 
-        // Create the relationship
+        // Create the relationship. `rel_type` is interpolated because Cypher does not allow
+        // relationship types to be bound as parameters; it was validated against an allowlist above.
         let query_str = format!(
-            "MATCH (a {{id: '{}'}}), (b {{id: '{}'}}) CREATE (a)-[:{}]->(b)",
-            start_id, end_id, rel_type
+            "MATCH (a {{id: $start_id}}), (b {{id: $end_id}}) CREATE (a)-[:{}]->(b)",
+            rel_type
         );
-        let q = query(&query_str);
+        let q = query(&query_str)
+            .param("start_id", start_id)
+            .param("end_id", end_id);
         debug!("Query_string: {}", query_str);
 
         match self.graph.run(q).await {
@@ -135,10 +214,12 @@ impl Neo4jClient {
 
         // Verify the relationship
         let verify_query_str = format!(
-            "MATCH (a {{id: '{}'}})-[r:{}]->(b {{id: '{}'}}) RETURN r",
-            start_id, rel_type, end_id
+            "MATCH (a {{id: $start_id}})-[r:{}]->(b {{id: $end_id}}) RETURN r",
+            rel_type
         );
-        let verify_q = query(&verify_query_str);
+        let verify_q = query(&verify_query_str)
+            .param("start_id", start_id)
+            .param("end_id", end_id);
         debug!("Verify Query_string: {}", verify_query_str);
 
         let mut result = self.graph.execute(verify_q).await?;
@@ -157,6 +238,76 @@ impl Neo4jClient {
         Ok(())
     }
 
+    /// Creates many relationships of a single `rel_type` in one transaction instead of a
+    /// sequential `create_relationship` + verify round trip per pair. `pairs` is
+    /// `(start_id, end_id, rel_type)`; all pairs sharing a `rel_type` are sent together as one
+    /// `UNWIND` so a batch of thousands of edges costs a single commit.
+    ///
+    /// Pairs whose `rel_type` fails the allowlist are skipped (not the whole call), and pairs
+    /// whose `start_id`/`end_id` don't resolve to an existing node are detected by diffing the
+    /// pairs the `CREATE` actually matched against the pairs requested, so a missing node drops
+    /// only that one relationship instead of being silently absorbed by the batch. Both kinds of
+    /// skipped pairs are returned so the caller can account for them (e.g. in metrics).
+    #[instrument(skip(self, pairs), fields(pair_count = pairs.len()))]
+    pub async fn create_relationships_batch(&self, pairs: &[(String, String, String)]) -> Result<Vec<(String, String, String)>, Neo4jClientError> {
+        if pairs.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut by_rel_type: HashMap<&str, Vec<(&String, &String)>> = HashMap::new();
+        let mut skipped = Vec::new();
+        for (start_id, end_id, rel_type) in pairs {
+            if validate_rel_type(rel_type).is_err() {
+                error!("Skipping pair ({}, {}): invalid relationship type {}", start_id, end_id, rel_type);
+                skipped.push((start_id.clone(), end_id.clone(), rel_type.clone()));
+                continue;
+            }
+            by_rel_type.entry(rel_type.as_str()).or_default().push((start_id, end_id));
+        }
+
+        let mut txn = self.graph.start_txn().await?;
+        for (rel_type, group) in &by_rel_type {
+            let pairs_param = BoltList::from(
+                group
+                    .iter()
+                    .map(|(start_id, end_id)| {
+                        BoltType::Map(BoltMap::from_iter([
+                            (BoltString::from("start"), BoltType::String(BoltString::from(start_id.as_str()))),
+                            (BoltString::from("end"), BoltType::String(BoltString::from(end_id.as_str()))),
+                        ]))
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            // `rel_type` was validated above; the pair list itself is bound as a parameter.
+            // Returning the matched pairs lets us tell which ones hit a missing node.
+            let query_str = format!(
+                "UNWIND $pairs AS p MATCH (a {{id: p.start}}), (b {{id: p.end}}) CREATE (a)-[:{}]->(b) RETURN p.start AS start, p.end AS end",
+                rel_type
+            );
+            let q = query(&query_str).param("pairs", BoltType::List(pairs_param));
+            debug!("Batch query_string for {}: {} ({} pairs)", rel_type, query_str, group.len());
+
+            let mut result = txn.execute(q).await?;
+            let mut created = std::collections::HashSet::new();
+            while let Ok(Some(row)) = result.next(&mut txn).await {
+                let start: String = row.get("start").unwrap_or_default();
+                let end: String = row.get("end").unwrap_or_default();
+                created.insert((start, end));
+            }
+
+            for (start_id, end_id) in group {
+                if !created.contains(&(start_id.to_string(), end_id.to_string())) {
+                    error!("Failed to create {} relationship between {} and {}: missing node(s)", rel_type, start_id, end_id);
+                    skipped.push((start_id.to_string(), end_id.to_string(), rel_type.to_string()));
+                }
+            }
+        }
+        txn.commit().await?;
+
+        Ok(skipped)
+    }
+
     pub async fn query_schema(&self) -> Result<String, Neo4jClientError> {
         let mut schema = String::new();
 
@@ -193,7 +344,7 @@ impl Neo4jClient {
         Ok(schema)
     }
 
-    pub async fn get_node_id_by_content(&self, content: &str) -> Result<Option<String>, Neo4jClientError> {
+    pub async fn get_internal_node_id_by_content(&self, content: &str) -> Result<Option<String>, Neo4jClientError> {
         let content_str = content.to_string();
         let query_str = "MATCH (n {content: $content}) RETURN n.id".to_string();
         let q = Query::new(query_str).param("content", content_str);
@@ -211,4 +362,105 @@ impl Neo4jClient {
             Err(e) => Err(Neo4jClientError::Neo4jError(e)),
         }
     }
+
+    /// Returns whether `end_id` is reachable from `start_id` by following only the given
+    /// relationship types, up to `max_depth` hops. Useful as a lightweight ReBAC-style
+    /// permission/ancestry check on top of the graph this crate already builds.
+    pub async fn is_reachable(
+        &self,
+        start_id: &str,
+        end_id: &str,
+        rel_types: &[String],
+        max_depth: u32,
+    ) -> Result<bool, Neo4jClientError> {
+        let query_str = reachability_query_str(rel_types, max_depth, true)?;
+        let q = query(&query_str)
+            .param("start", start_id)
+            .param("end", end_id);
+        debug!("Reachability query_string: {}", query_str);
+
+        let mut result = self.graph.execute(q).await?;
+        Ok(result.next().await?.is_some())
+    }
+
+    /// Returns the ids of every node reachable from `start_id` by following only the given
+    /// relationship types, up to `max_depth` hops.
+    pub async fn reachable_nodes(
+        &self,
+        start_id: &str,
+        rel_types: &[String],
+        max_depth: u32,
+    ) -> Result<Vec<String>, Neo4jClientError> {
+        let query_str = reachability_query_str(rel_types, max_depth, false)?;
+        let q = query(&query_str).param("start", start_id);
+        debug!("Reachability query_string: {}", query_str);
+
+        let mut result = self.graph.execute(q).await?;
+        let mut ids = Vec::new();
+        while let Ok(Some(row)) = result.next().await {
+            if let Ok(id) = row.get::<String>("b.id") {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
+}
+
+/// Builds the variable-length path query shared by `is_reachable` and `reachable_nodes`. The
+/// relationship type union and the depth bound cannot be bound as parameters in Cypher, so both
+/// are interpolated after validation; the node ids are passed as real bind parameters.
+fn reachability_query_str(
+    rel_types: &[String],
+    max_depth: u32,
+    match_end: bool,
+) -> Result<String, Neo4jClientError> {
+    for rel_type in rel_types {
+        validate_rel_type(rel_type)?;
+    }
+    let types = rel_types.join("|");
+
+    Ok(if match_end {
+        format!(
+            "MATCH (a {{id: $start}})-[:{}*1..{}]->(b {{id: $end}}) RETURN DISTINCT b.id LIMIT 1",
+            types, max_depth
+        )
+    } else {
+        format!(
+            "MATCH (a {{id: $start}})-[:{}*1..{}]->(b) RETURN DISTINCT b.id",
+            types, max_depth
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_screaming_snake_case() {
+        assert!(validate_rel_type("SIMILAR_TO").is_ok());
+        assert!(validate_rel_type("A").is_ok());
+        assert!(validate_rel_type("_LEADING_UNDERSCORE").is_ok());
+    }
+
+    #[test]
+    fn rejects_lowercase() {
+        assert!(validate_rel_type("similar_to").is_err());
+    }
+
+    #[test]
+    fn rejects_punctuation() {
+        assert!(validate_rel_type("SIMILAR-TO").is_err());
+        assert!(validate_rel_type("SIMILAR TO").is_err());
+    }
+
+    #[test]
+    fn rejects_empty() {
+        assert!(validate_rel_type("").is_err());
+    }
+
+    #[test]
+    fn rejects_leading_digit() {
+        assert!(validate_rel_type("1SIMILAR_TO").is_err());
+    }
 }
@@ -0,0 +1,118 @@
+use oxigraph::io::RdfFormat;
+use oxigraph::model::{GraphNameRef, Literal, NamedNode, Quad};
+use oxigraph::sparql::QueryResults;
+use oxigraph::store::Store;
+use thiserror::Error;
+
+use crate::neo4j_client::{QueryResult, RelationshipRecord};
+
+#[derive(Error, Debug)]
+pub enum RdfError {
+    #[error("Oxigraph store error: {0}")]
+    StoreError(#[from] oxigraph::store::StorageError),
+    #[error("Oxigraph SPARQL evaluation error: {0}")]
+    EvaluationError(#[from] oxigraph::sparql::EvaluationError),
+    #[error("Oxigraph load error: {0}")]
+    LoaderError(#[from] oxigraph::store::LoaderError),
+    #[error("Oxigraph serialization error: {0}")]
+    SerializerError(#[from] oxigraph::store::SerializerError),
+    #[error("Invalid IRI: {0}")]
+    InvalidIri(String),
+}
+
+fn node_iri(id: &str) -> String {
+    format!("urn:rfnu:node:{}", id)
+}
+
+/// One shared vocabulary term per property *name*, not per subject, so a SPARQL query can
+/// filter or join across nodes on a property (e.g. `?n <urn:rfnu:prop:content> "x"`). Minting
+/// this per-subject instead (`urn:rfnu:node:{id}#{property}`) would give every node its own
+/// predicate and make that impossible.
+fn prop_iri(property: &str) -> String {
+    format!("urn:rfnu:prop:{}", property)
+}
+
+fn rel_iri(rel_type: &str) -> String {
+    format!("urn:rfnu:rel:{}", rel_type)
+}
+
+/// Serializes queried nodes to N-Triples, one triple per property:
+/// `<urn:rfnu:node:{id}> <urn:rfnu:prop:{property}> "{value}" .`
+pub fn nodes_to_ntriples(nodes: &[QueryResult]) -> Result<String, RdfError> {
+    let mut out = String::new();
+    for node in nodes {
+        let id = node
+            .properties
+            .iter()
+            .find(|p| p.name == "id")
+            .map(|p| p.value.as_str())
+            .unwrap_or(&node.entity);
+        let subject = NamedNode::new(node_iri(id)).map_err(|e| RdfError::InvalidIri(e.to_string()))?;
+
+        for prop in &node.properties {
+            let predicate = NamedNode::new(prop_iri(&prop.name)).map_err(|e| RdfError::InvalidIri(e.to_string()))?;
+            let object = Literal::new_simple_literal(&prop.value);
+            let quad = Quad::new(subject.clone(), predicate, object, GraphNameRef::DefaultGraph);
+            out.push_str(&quad.to_string());
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Serializes relationships to N-Triples: `<urn:rfnu:node:{start}> <urn:rfnu:rel:{TYPE}> <urn:rfnu:node:{end}> .`
+pub fn relationships_to_ntriples(relationships: &[RelationshipRecord]) -> Result<String, RdfError> {
+    let mut out = String::new();
+    for rel in relationships {
+        let subject = NamedNode::new(node_iri(&rel.start_id)).map_err(|e| RdfError::InvalidIri(e.to_string()))?;
+        let predicate = NamedNode::new(rel_iri(&rel.rel_type)).map_err(|e| RdfError::InvalidIri(e.to_string()))?;
+        let object = NamedNode::new(node_iri(&rel.end_id)).map_err(|e| RdfError::InvalidIri(e.to_string()))?;
+        let quad = Quad::new(subject, predicate, object, GraphNameRef::DefaultGraph);
+        out.push_str(&quad.to_string());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Serializes the full graph (nodes and relationships) to N-Triples.
+pub fn graph_to_ntriples(nodes: &[QueryResult], relationships: &[RelationshipRecord]) -> Result<String, RdfError> {
+    let mut out = nodes_to_ntriples(nodes)?;
+    out.push_str(&relationships_to_ntriples(relationships)?);
+    Ok(out)
+}
+
+/// An in-memory oxigraph store loaded from the constructed graph, queryable via SPARQL so
+/// callers can run graph-pattern queries (e.g. nodes two `PART_OF` hops below a heading)
+/// without Cypher.
+pub struct RdfStore {
+    store: Store,
+}
+
+impl RdfStore {
+    /// Builds an in-memory store and loads it with the N-Triples serialization of `nodes` and
+    /// `relationships`.
+    pub fn from_graph(nodes: &[QueryResult], relationships: &[RelationshipRecord]) -> Result<Self, RdfError> {
+        let store = Store::new()?;
+        let ntriples = graph_to_ntriples(nodes, relationships)?;
+        store.load_from_reader(RdfFormat::NTriples, ntriples.as_bytes())?;
+        Ok(Self { store })
+    }
+
+    /// Dumps the store's contents back out as N-Triples, e.g. for handing off to other
+    /// semantic-web tooling.
+    pub fn to_ntriples(&self) -> Result<Vec<u8>, RdfError> {
+        Ok(self.store.dump_to_writer(RdfFormat::NTriples, Vec::new())?)
+    }
+
+    /// Runs a SPARQL query against the loaded graph. `SELECT`/`ASK` results are returned as
+    /// SPARQL JSON results; `CONSTRUCT`/`DESCRIBE` results are returned as N-Triples.
+    pub fn run_sparql(&self, query: &str) -> Result<String, RdfError> {
+        let results = self.store.query(query)?;
+        let bytes = if matches!(results, QueryResults::Graph(_)) {
+            results.write_graph(Vec::new(), RdfFormat::NTriples)?
+        } else {
+            results.write(Vec::new(), oxigraph::sparql::results::QueryResultsFormat::Json)?
+        };
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}